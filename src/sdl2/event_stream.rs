@@ -0,0 +1,262 @@
+//! An optional bridge from SDL's event queue to a `futures::Stream`.
+//!
+//! Enabled with the `async-stream` feature. Applications built on an async
+//! runtime (tokio, async-std, ...) can `.await` an `EventStream` for input
+//! instead of hand-rolling a loop around `EventPump::poll_iter`.
+//!
+//! `SDL_PumpEvents` may only run on SDL's main thread, and once a `.await` on
+//! this stream parks, nothing would otherwise come back to call it - so
+//! `EventStream` drives itself. Construction starts an `SDL_AddTimer` that
+//! fires every `DRIVE_INTERVAL_MS` off an SDL-internal timer thread. That
+//! thread can't pump (pumping is main-thread-only), so instead it pushes a
+//! private marker event via `SDL_PushEvent`, which *is* thread-safe and wakes
+//! a parked poll exactly the way any other `SDL_PushEvent`-delivered event
+//! does. When the stream is next polled - always on the main thread, since
+//! `EventStream` (via its `EventPump`) is `!Send`, so nothing holding it can
+//! live anywhere else - `poll_next` pumps before checking for new input,
+//! picking up whatever real OS/device input has piled up since. The marker
+//! event itself carries no payload and is filtered out before it would ever
+//! reach an application.
+
+#![cfg(feature = "async-stream")]
+
+use std::collections::VecDeque;
+use std::mem;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use futures::stream::Stream;
+use libc::{c_int, c_void};
+
+use event::Event;
+use sdl::EventPump;
+use sys::sdl as ll;
+
+/// How often the internal timer wakes the stream to pump for new input.
+const DRIVE_INTERVAL_MS: u32 = 16;
+
+/// State shared between the `EventStream` and the SDL event watch callback.
+///
+/// The callback takes the `ring` lock to append an event and then the `waker`
+/// lock to take the stored waker, never holding both at once; each lock is
+/// held only for a single push, pop or swap on either side.
+struct Shared {
+    ring: Mutex<VecDeque<Event>>,
+    waker: Mutex<Option<Waker>>,
+    // Set by the watch callback when it fires and finds no waker stored yet,
+    // so `poll_next` can notice the race instead of missing the wakeup.
+    woken: AtomicBool,
+    // The event type reserved via `SDL_RegisterEvents` for the drive timer's
+    // marker event, so the watch callback can recognize and swallow it
+    // instead of handing it to the application as real input.
+    drive_event_type: u32
+}
+
+impl Shared {
+    fn wake(&self) {
+        if let Some(waker) = self.waker.lock().unwrap().take() {
+            waker.wake();
+        } else {
+            self.woken.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// Bridges SDL's event queue to a `futures::Stream<Item = Event>`.
+///
+/// Internally this registers an `SDL_AddEventWatch` callback that pushes every
+/// incoming event onto a small ring buffer and wakes whichever task is
+/// polling the stream, plus an `SDL_AddTimer` that keeps that wakeup firing
+/// on a cadence even when nothing else is polling - see the module docs for
+/// why both pieces are needed.
+///
+/// Dropping the `EventStream` removes the watch and the timer and returns the
+/// underlying `EventPump`'s single-instance slot, just like dropping an
+/// `EventPump` does.
+pub struct EventStream {
+    _event_pump: EventPump,
+    shared: Arc<Shared>,
+    watch_userdata: *mut Shared,
+    timer_id: ll::SDL_TimerID
+}
+
+impl EventStream {
+    /// Creates an `EventStream`, taking ownership of the process's `EventPump`.
+    ///
+    /// Only one `EventStream` (like only one `EventPump`) may be alive at a time.
+    pub fn new(event_pump: EventPump) -> EventStream {
+        unsafe {
+            // Just in case none of the other subsystems have initialized it
+            // yet - needed for `SDL_AddTimer` below.
+            ll::SDL_InitSubSystem(ll::SDL_INIT_TIMER);
+        }
+
+        // A type ID reserved just for us, so the watch callback can tell our
+        // own drive marker apart from any real application user event.
+        let drive_event_type = unsafe { ll::SDL_RegisterEvents(1) };
+
+        let shared = Arc::new(Shared {
+            ring: Mutex::new(VecDeque::new()),
+            waker: Mutex::new(None),
+            woken: AtomicBool::new(false),
+            drive_event_type: drive_event_type
+        });
+
+        // Handed to SDL as the watch callback's userdata; reclaimed in `Drop`.
+        let watch_userdata = Arc::into_raw(shared.clone()) as *mut Shared;
+
+        unsafe {
+            ll::SDL_AddEventWatch(Some(event_watch), watch_userdata as *mut c_void);
+        }
+
+        // `drive_event_type` is handed to the timer callback as its `param`,
+        // stashed directly in the pointer rather than via `Shared` - the
+        // timer thread only ever needs the type it's pushing, never the ring.
+        let timer_id = unsafe {
+            ll::SDL_AddTimer(DRIVE_INTERVAL_MS, Some(drive_timer), drive_event_type as usize as *mut c_void)
+        };
+
+        EventStream {
+            _event_pump: event_pump,
+            shared: shared,
+            watch_userdata: watch_userdata,
+            timer_id: timer_id
+        }
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Event;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Event>> {
+        let this = self.get_mut();
+
+        // Safe to pump here: `EventStream` holds an `EventPump`, which is
+        // `!Send`, so this future can only ever live on - and be polled from
+        // - the thread that created it, i.e. SDL's main thread.
+        unsafe { ll::SDL_PumpEvents(); }
+
+        if let Some(event) = this.shared.ring.lock().unwrap().pop_front() {
+            return Poll::Ready(Some(event));
+        }
+
+        *this.shared.waker.lock().unwrap() = Some(cx.waker().clone());
+
+        // The watch callback may have fired between the `pop_front` above and
+        // storing the waker; `woken` catches that race so we don't miss it.
+        if this.shared.woken.swap(false, Ordering::AcqRel) {
+            if let Some(event) = this.shared.ring.lock().unwrap().pop_front() {
+                return Poll::Ready(Some(event));
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        unsafe {
+            ll::SDL_RemoveTimer(self.timer_id);
+            ll::SDL_DelEventWatch(Some(event_watch), self.watch_userdata as *mut c_void);
+            // Reclaim the `Arc` clone that was handed to SDL as userdata.
+            drop(Arc::from_raw(self.watch_userdata as *const Shared));
+            ll::SDL_QuitSubSystem(ll::SDL_INIT_TIMER);
+        }
+    }
+}
+
+unsafe extern "C" fn event_watch(userdata: *mut c_void, event: *mut ll::SDL_Event) -> c_int {
+    let shared = &*(userdata as *const Shared);
+
+    if (*event).type_ == shared.drive_event_type {
+        // Our own wakeup marker, not real input - wake whoever's parked, but
+        // never let it reach the application.
+        shared.wake();
+        return 1;
+    }
+
+    if let Some(event) = Event::from_ll(*event) {
+        shared.ring.lock().unwrap().push_back(event);
+        shared.wake();
+    }
+
+    1
+}
+
+/// Fires every `DRIVE_INTERVAL_MS` on an SDL-internal timer thread. Can't
+/// pump from here - pumping is main-thread-only - so it just pushes a marker
+/// event, which `SDL_PushEvent` delivers to `event_watch` in a thread-safe way.
+unsafe extern "C" fn drive_timer(interval: u32, param: *mut c_void) -> u32 {
+    let drive_event_type = param as usize as u32;
+
+    let mut raw: ll::SDL_Event = mem::zeroed();
+    raw.type_ = drive_event_type;
+    ll::SDL_PushEvent(&mut raw);
+
+    // Returning the same interval keeps the timer repeating.
+    interval
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::task::noop_waker;
+
+    use super::*;
+
+    // `SDL_PushEvent` wakes a parked poll directly, with no pump involved -
+    // this is the delivery path real device input would also take once the
+    // drive timer's marker event wakes `poll_next` to pump and check again.
+    #[test]
+    fn a_pushed_event_wakes_a_parked_poll() {
+        let sdl = ::sdl::init().unwrap();
+        let event_pump = sdl.event_pump().unwrap();
+        let mut stream = EventStream::new(event_pump);
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        // Nothing queued yet: parks and stores a waker.
+        match Pin::new(&mut stream).poll_next(&mut cx) {
+            Poll::Pending => {}
+            _ => panic!("expected the stream to park with nothing queued"),
+        }
+
+        unsafe {
+            let mut raw: ll::SDL_Event = ::std::mem::zeroed();
+            raw.type_ = ll::SDL_KEYDOWN;
+            ll::SDL_PushEvent(&mut raw);
+        }
+
+        match Pin::new(&mut stream).poll_next(&mut cx) {
+            Poll::Ready(Some(_)) => {}
+            Poll::Ready(None) => panic!("stream ended before the pushed event was seen"),
+            Poll::Pending => panic!("the pushed event never reached the ring buffer"),
+        }
+    }
+
+    // The drive timer's marker event must never surface to the application,
+    // however many times it fires while the stream is parked.
+    #[test]
+    fn the_drive_timer_marker_never_reaches_the_application() {
+        use std::thread;
+        use std::time::Duration;
+
+        let sdl = ::sdl::init().unwrap();
+        let event_pump = sdl.event_pump().unwrap();
+        let mut stream = EventStream::new(event_pump);
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        // Give the timer a few chances to fire.
+        thread::sleep(Duration::from_millis(DRIVE_INTERVAL_MS as u64 * 4));
+
+        match Pin::new(&mut stream).poll_next(&mut cx) {
+            Poll::Pending => {}
+            Poll::Ready(event) => panic!("expected no real input, got {:?}", event),
+        }
+    }
+}
@@ -1,5 +1,10 @@
+use std::cell::RefCell;
+use std::error::Error as StdError;
 use std::ffi::{CStr, CString, NulError};
-use std::rc::Rc;
+use std::fmt;
+use std::rc::{Rc, Weak};
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{channel, Receiver, Sender};
 use libc::c_char;
 
 use sys::sdl as ll;
@@ -13,11 +18,138 @@ pub enum Error {
     UnsupportedError = ll::SDL_UNSUPPORTED as isize
 }
 
+impl Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::NoMemError => "out of memory",
+            Error::ReadError => "error reading from datastream",
+            Error::WriteError => "error writing to datastream",
+            Error::SeekError => "error seeking in datastream",
+            Error::UnsupportedError => "unknown SDL error"
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.description())
+    }
+}
+
+impl StdError for Error {
+    fn description(&self) -> &str {
+        self.description()
+    }
+}
+
+/// The specific kind of failure that produced an `SdlError`.
+///
+/// This deliberately does *not* let callers match on SDL's internal
+/// `SDL_errorcode` classification (`SDL_ENOMEM`, `SDL_EFREAD`, and so on) the
+/// way `Error` above does for `set_error_from_code`. That direction only
+/// works because *we* choose the code when raising the error ourselves;
+/// recovering one from an arbitrary SDL failure would require the reverse -
+/// and `SDL_GetError()` only ever hands back the rendered message string,
+/// with no accompanying code and no public API to ask SDL which one it used.
+/// Matching a message against the fixed strings SDL's own `SDL_Error` uses
+/// per code (see `Error::description`) would work by coincidence for the
+/// codes this crate already tracks, and not at all for the many internal
+/// SDL failures that were never routed through `SDL_Error` in the first
+/// place - a worse bet than just reading `message()`. So `SdlErrorKind` only
+/// distinguishes which of *our* calls failed (`Init`, `SubsystemInit`, an
+/// already-alive singleton); `message()` is the only source of SDL's own
+/// explanation, and it stays a plain `String`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SdlErrorKind {
+    /// `SDL_Init` failed.
+    Init,
+    /// `SDL_InitSubSystem` failed for the given subsystem flag.
+    SubsystemInit { flag: ll::SDL_InitFlag },
+    /// An `Sdl`, `EventPump` or `EventLoop` instance was already alive.
+    AlreadyAlive
+}
+
+/// An error produced while initializing SDL or one of its subsystems.
+///
+/// Carries both the kind of failure (`kind()`, an `SdlErrorKind` - which of
+/// our calls failed) and the message SDL itself reported (`message()`,
+/// `SDL_GetError()`'s text at the time of failure). There's no third,
+/// structured piece to match on beyond those two: see `SdlErrorKind`'s docs
+/// for why recovering SDL's own `SDL_errorcode` isn't possible here.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SdlError {
+    kind: SdlErrorKind,
+    message: String
+}
+
+impl SdlError {
+    fn new(kind: SdlErrorKind, message: String) -> SdlError {
+        SdlError { kind: kind, message: message }
+    }
+
+    /// The kind of failure that occurred.
+    pub fn kind(&self) -> SdlErrorKind {
+        self.kind
+    }
+
+    /// The message captured from `SDL_GetError()` when this error occurred.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Best-effort check for whether this error's message reads the way
+    /// SDL's own `SDL_Error` would have worded `err`.
+    ///
+    /// This is the closest thing to matching against a structured
+    /// `SDL_errorcode` this crate can offer - see `SdlErrorKind`'s docs for
+    /// why a real one isn't available. It only helps for the handful of
+    /// codes `Error` tracks, says nothing about SDL failures that were never
+    /// routed through `SDL_Error` to begin with, and would break if a future
+    /// SDL release reworded its messages. Prefer matching on `kind()` where
+    /// that's enough to distinguish what you need; reach for this only when
+    /// you specifically need to compare against one of `Error`'s codes.
+    pub fn matches_description(&self, err: Error) -> bool {
+        self.message == err.description()
+    }
+}
+
+impl fmt::Display for SdlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl StdError for SdlError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+#[cfg(test)]
+mod sdl_error_tests {
+    use super::*;
+
+    #[test]
+    fn matches_description_compares_against_the_sdl_error_wording() {
+        let err = SdlError::new(SdlErrorKind::Init, "out of memory".to_owned());
+
+        assert!(err.matches_description(Error::NoMemError));
+        assert!(!err.matches_description(Error::ReadError));
+    }
+}
+
 use std::sync::atomic::{AtomicBool, ATOMIC_BOOL_INIT};
 /// Only one Sdl context can be alive at a time.
 /// Set to false by default (not alive).
 static IS_SDL_CONTEXT_ALIVE: AtomicBool = ATOMIC_BOOL_INIT;
 
+thread_local! {
+    /// A weak handle to the `Sdl` context active on this thread, if `init()` has
+    /// been called here and the context hasn't been dropped since. Kept weak so
+    /// this slot can't keep SDL alive on its own.
+    static CURRENT_SDL: RefCell<Option<Weak<SdlDrop>>> = RefCell::new(None);
+}
+
 /// The SDL context type. Initialize with `sdl2::init()`.
 ///
 /// From a thread-safety perspective, `Sdl` represents the main thread.
@@ -37,7 +169,7 @@ pub struct Sdl {
 
 impl Sdl {
     #[inline]
-    fn new() -> Result<Sdl, String> {
+    fn new() -> Result<Sdl, SdlError> {
         unsafe {
             use std::sync::atomic::Ordering;
 
@@ -45,16 +177,24 @@ impl Sdl {
             let was_alive = IS_SDL_CONTEXT_ALIVE.swap(true, Ordering::Relaxed);
 
             if was_alive {
-                Err("Cannot initialize `Sdl` more than once at a time.".to_owned())
+                Err(SdlError::new(SdlErrorKind::AlreadyAlive,
+                                   "Cannot initialize `Sdl` more than once at a time.".to_owned()))
             } else {
                 // Initialize SDL without any explicit subsystems (flags = 0).
                 if ll::SDL_Init(0) == 0 {
-                    Ok(Sdl {
-                        sdldrop: Rc::new(SdlDrop)
-                    })
+                    let (main_thread_sender, main_thread_receiver) = channel();
+
+                    let sdldrop = Rc::new(SdlDrop {
+                        main_thread_sender: Arc::new(Mutex::new(main_thread_sender)),
+                        main_thread_receiver: Mutex::new(Some(main_thread_receiver))
+                    });
+
+                    CURRENT_SDL.with(|cell| *cell.borrow_mut() = Some(Rc::downgrade(&sdldrop)));
+
+                    Ok(Sdl { sdldrop: sdldrop })
                 } else {
                     IS_SDL_CONTEXT_ALIVE.swap(false, Ordering::Relaxed);
-                    Err(get_error())
+                    Err(SdlError::new(SdlErrorKind::Init, get_error()))
                 }
             }
         }
@@ -62,31 +202,31 @@ impl Sdl {
 
     /// Initializes the audio subsystem.
     #[inline]
-    pub fn audio(&self) -> Result<AudioSubsystem, String> { AudioSubsystem::new(self) }
+    pub fn audio(&self) -> Result<AudioSubsystem, SdlError> { AudioSubsystem::new(self) }
 
     /// Initializes the event subsystem.
     #[inline]
-    pub fn event(&self) -> Result<EventSubsystem, String> { EventSubsystem::new(self) }
+    pub fn event(&self) -> Result<EventSubsystem, SdlError> { EventSubsystem::new(self) }
 
     /// Initializes the joystick subsystem.
     #[inline]
-    pub fn joystick(&self) -> Result<JoystickSubsystem, String> { JoystickSubsystem::new(self) }
+    pub fn joystick(&self) -> Result<JoystickSubsystem, SdlError> { JoystickSubsystem::new(self) }
 
     /// Initializes the haptic subsystem.
     #[inline]
-    pub fn haptic(&self) -> Result<HapticSubsystem, String> { HapticSubsystem::new(self) }
+    pub fn haptic(&self) -> Result<HapticSubsystem, SdlError> { HapticSubsystem::new(self) }
 
     /// Initializes the game controller subsystem.
     #[inline]
-    pub fn game_controller(&self) -> Result<GameControllerSubsystem, String> { GameControllerSubsystem::new(self) }
+    pub fn game_controller(&self) -> Result<GameControllerSubsystem, SdlError> { GameControllerSubsystem::new(self) }
 
     /// Initializes the timer subsystem.
     #[inline]
-    pub fn timer(&self) -> Result<TimerSubsystem, String> { TimerSubsystem::new(self) }
+    pub fn timer(&self) -> Result<TimerSubsystem, SdlError> { TimerSubsystem::new(self) }
 
     /// Initializes the video subsystem.
     #[inline]
-    pub fn video(&self) -> Result<VideoSubsystem, String> { VideoSubsystem::new(self) }
+    pub fn video(&self) -> Result<VideoSubsystem, SdlError> { VideoSubsystem::new(self) }
 
     /// Obtains the SDL event pump.
     ///
@@ -94,21 +234,95 @@ impl Sdl {
     /// If this function is called while an `EventPump` instance is alive, the function will return
     /// an error.
     #[inline]
-    pub fn event_pump(&self) -> Result<EventPump, String> {
+    pub fn event_pump(&self) -> Result<EventPump, SdlError> {
         EventPump::new(self)
     }
 
+    /// Obtains a built-in `EventLoop` driving a fresh `EventPump`.
+    ///
+    /// At most one `EventLoop` (and thus one `EventPump`) is allowed to be alive at a time.
+    #[inline]
+    pub fn event_loop(&self) -> Result<EventLoop, SdlError> {
+        let event_pump = try!(self.event_pump());
+        EventLoop::new(self, event_pump)
+    }
+
+    /// Obtains a `Send + Sync` handle that can be used from any thread to schedule
+    /// closures to run on this `Sdl`'s main thread.
+    ///
+    /// Jobs scheduled through the returned `MainThreadProxy` sit in a queue
+    /// until something on the main thread calls `EventPump::run_main_thread_jobs`
+    /// to drain it - `EventLoop::run` does this automatically every iteration;
+    /// a bare `EventPump` does not drain it on its own, so callers driving
+    /// their own loop must call `run_main_thread_jobs` themselves.
+    #[inline]
+    pub fn main_thread_proxy(&self) -> MainThreadProxy {
+        MainThreadProxy {
+            sender: self.sdldrop.main_thread_sender.clone()
+        }
+    }
+
     #[inline]
     #[doc(hidden)]
     pub fn sdldrop(&self) -> Rc<SdlDrop> {
         self.sdldrop.clone()
     }
+
+    /// Returns the `Sdl` context active on the current thread, if any.
+    ///
+    /// This lets deeply nested code on the main thread reconstruct the
+    /// context without it being passed down explicitly. Returns `None` if
+    /// called on a thread other than the one `sdl2::init()` was called on, or
+    /// after that context has since been dropped (`SDL_Quit` called) - it
+    /// never panics.
+    pub fn current() -> Option<Sdl> {
+        CURRENT_SDL.with(|cell| {
+            cell.borrow()
+                .as_ref()
+                .and_then(Weak::upgrade)
+                .map(|sdldrop| Sdl { sdldrop: sdldrop })
+        })
+    }
+}
+
+/// A job scheduled on a `MainThreadProxy`, to be run with a borrow of the main
+/// thread's `Sdl` context.
+type MainThreadJob = Box<FnOnce(&Sdl) + Send>;
+
+/// A `Send + Sync` handle that lets other threads ask the main thread to run a
+/// closure against the main-thread `Sdl` context.
+///
+/// Subsystems are deliberately `!Send`, so worker threads have no direct way to
+/// touch video/window/renderer state. A `MainThreadProxy`, obtained via
+/// `Sdl::main_thread_proxy`, instead queues the closure for the main thread to
+/// run the next time it calls `EventPump::run_main_thread_jobs` - which
+/// `EventLoop::run` does every iteration, but a bare `EventPump` does not do
+/// on its own.
+#[derive(Clone)]
+pub struct MainThreadProxy {
+    sender: Arc<Mutex<Sender<MainThreadJob>>>
+}
+
+impl MainThreadProxy {
+    /// Schedules `job` to run on the main thread.
+    ///
+    /// The closure is queued and is not run until the main thread's `EventPump`
+    /// next drains its queue; it is never run on the calling thread. Once the
+    /// `Sdl` context has been dropped, the job is discarded without running.
+    pub fn schedule<F>(&self, job: F) where F: FnOnce(&Sdl) + Send + 'static {
+        let sender = self.sender.lock().unwrap();
+        // The receiving end lives in `SdlDrop`, so this fails only after the
+        // `Sdl` context is gone; the job is then dropped unrun.
+        let _ = sender.send(Box::new(job));
+    }
 }
 
 /// When SDL is no longer in use (the refcount in an `Rc<SdlDrop>` reaches 0), the library is quit.
 #[doc(hidden)]
-#[derive(Debug)]
-pub struct SdlDrop;
+pub struct SdlDrop {
+    main_thread_sender: Arc<Mutex<Sender<MainThreadJob>>>,
+    main_thread_receiver: Mutex<Option<Receiver<MainThreadJob>>>
+}
 
 impl Drop for SdlDrop {
     #[inline]
@@ -118,6 +332,8 @@ impl Drop for SdlDrop {
         let was_alive = IS_SDL_CONTEXT_ALIVE.swap(false, Ordering::Relaxed);
         assert!(was_alive);
 
+        CURRENT_SDL.with(|cell| *cell.borrow_mut() = None);
+
         unsafe { ll::SDL_Quit(); }
     }
 }
@@ -131,7 +347,7 @@ macro_rules! subsystem {
     ($name:ident, $flag:expr) => (
         impl $name {
             #[inline]
-            fn new(sdl: &Sdl) -> Result<$name, String> {
+            fn new(sdl: &Sdl) -> Result<$name, SdlError> {
                 let result = unsafe { ll::SDL_InitSubSystem($flag) };
 
                 if result == 0 {
@@ -142,7 +358,7 @@ macro_rules! subsystem {
                         })
                     })
                 } else {
-                    Err(get_error())
+                    Err(SdlError::new(SdlErrorKind::SubsystemInit { flag: $flag }, get_error()))
                 }
             }
         }
@@ -221,18 +437,20 @@ static mut IS_EVENT_PUMP_ALIVE: bool = false;
 
 /// A thread-safe type that encapsulates SDL event-pumping functions.
 pub struct EventPump {
-    _sdldrop: Rc<SdlDrop>
+    _sdldrop: Rc<SdlDrop>,
+    main_thread_receiver: Option<Receiver<MainThreadJob>>
 }
 
 impl EventPump {
     /// Obtains the SDL event pump.
     #[inline]
-    fn new(sdl: &Sdl) -> Result<EventPump, String> {
+    fn new(sdl: &Sdl) -> Result<EventPump, SdlError> {
         // Called on the main SDL thread.
 
         unsafe {
             if IS_EVENT_PUMP_ALIVE {
-                Err("an `EventPump` instance is already alive - there can only be one `EventPump` in use at a time.".to_owned())
+                Err(SdlError::new(SdlErrorKind::AlreadyAlive,
+                                   "an `EventPump` instance is already alive - there can only be one `EventPump` in use at a time.".to_owned()))
             } else {
                 // Initialize the events subsystem, just in case none of the other subsystems have done it yet.
                 let result = ll::SDL_InitSubSystem(ll::SDL_INIT_EVENTS);
@@ -240,15 +458,36 @@ impl EventPump {
                 if result == 0 {
                     IS_EVENT_PUMP_ALIVE = true;
 
+                    // Taken back out by `Drop`, so a later `EventPump` can reuse it.
+                    let main_thread_receiver = sdl.sdldrop.main_thread_receiver.lock().unwrap()
+                        .take().expect("main thread job queue is missing");
+
                     Ok(EventPump {
-                        _sdldrop: sdl.sdldrop.clone()
+                        _sdldrop: sdl.sdldrop.clone(),
+                        main_thread_receiver: Some(main_thread_receiver)
                     })
                 } else {
-                    Err(get_error())
+                    Err(SdlError::new(SdlErrorKind::SubsystemInit { flag: ll::SDL_INIT_EVENTS }, get_error()))
                 }
             }
         }
     }
+
+    /// Runs every job currently queued on this `Sdl`'s `MainThreadProxy`, passing
+    /// each one a borrow of the main-thread `sdl` context.
+    ///
+    /// This chunk does not define `poll_iter`/`wait_event` itself, so nothing
+    /// calls this for you automatically - `EventLoop::run` calls it once per
+    /// iteration, but if you drive a bare `EventPump` with your own loop, you
+    /// must call this yourself (e.g. once per pump) or jobs scheduled on a
+    /// `MainThreadProxy` will sit in the queue forever.
+    pub fn run_main_thread_jobs(&mut self, sdl: &Sdl) {
+        if let Some(ref receiver) = self.main_thread_receiver {
+            while let Ok(job) = receiver.try_recv() {
+                job(sdl);
+            }
+        }
+    }
 }
 
 impl Drop for EventPump {
@@ -261,6 +500,344 @@ impl Drop for EventPump {
             ll::SDL_QuitSubSystem(ll::SDL_INIT_EVENTS);
             IS_EVENT_PUMP_ALIVE = false;
         }
+
+        // Hand the receiver back, so a later `EventPump` can pick up where this one left off.
+        let receiver = self.main_thread_receiver.take();
+        *self._sdldrop.main_thread_receiver.lock().unwrap() = receiver;
+    }
+}
+
+static mut IS_EVENT_LOOP_ALIVE: bool = false;
+
+/// A `Send + Sync + Clone` handle for pausing, resuming, or quitting an
+/// `EventLoop` from anywhere: from inside its own `run` callback, from a job
+/// scheduled via a `MainThreadProxy`, or from another thread entirely (e.g.
+/// stashed before calling `run` and signalled when the app is backgrounded).
+///
+/// This is the reachable control path the plain `EventLoop` methods alone
+/// don't provide: `run` holds `&mut EventLoop` for as long as it's looping,
+/// so nothing outside of `run`'s own callback can otherwise reach `pause`,
+/// `resume`, or `quit` while it's in progress.
+#[derive(Clone)]
+pub struct EventLoopControl {
+    paused: Arc<AtomicBool>,
+    should_quit: Arc<AtomicBool>,
+    should_flush: Arc<AtomicBool>
+}
+
+impl EventLoopControl {
+    /// Requests that the loop pause after the current iteration finishes.
+    ///
+    /// Events are still accepted into the SDL queue while paused; they are
+    /// simply not delivered to `callback` until `resume` is called.
+    #[inline]
+    pub fn pause(&self) {
+        use std::sync::atomic::Ordering;
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    /// Resumes invoking `callback` once per iteration.
+    #[inline]
+    pub fn resume(&self) {
+        use std::sync::atomic::Ordering;
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    /// Requests that the events queued so far be purged before the next
+    /// iteration starts, then delivery resume (undoing any prior `pause`).
+    ///
+    /// The actual `SDL_FlushEvents` call happens on `run`'s own thread, the
+    /// next time it checks in between iterations - `SDL_FlushEvents` itself
+    /// isn't safe to call from just anywhere, so this only ever records the
+    /// request, the same way `pause`/`resume`/`quit` do.
+    #[inline]
+    pub fn flush(&self) {
+        use std::sync::atomic::Ordering;
+        self.should_flush.store(true, Ordering::Relaxed);
+    }
+
+    /// Requests that `run` stop after the current iteration finishes.
+    #[inline]
+    pub fn quit(&self) {
+        use std::sync::atomic::Ordering;
+        self.should_quit.store(true, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn is_paused(&self) -> bool {
+        use std::sync::atomic::Ordering;
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    fn should_quit(&self) -> bool {
+        use std::sync::atomic::Ordering;
+        self.should_quit.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    fn take_should_flush(&self) -> bool {
+        use std::sync::atomic::Ordering;
+        self.should_flush.swap(false, Ordering::Relaxed)
+    }
+}
+
+/// A built-in runner that drives an `EventPump` to completion, invoking a
+/// user callback once per iteration.
+///
+/// This replaces hand-rolling a `'running: loop { for event in
+/// event_pump.poll_iter() { ... } }`, and adds `pause`/`resume`/`flush` with
+/// deterministic semantics: pausing never interrupts an iteration that is
+/// already in progress (it only takes effect before the next one starts),
+/// and flushing purges only the events queued up to that point, without
+/// losing events that arrive afterwards.
+pub struct EventLoop {
+    _sdldrop: Rc<SdlDrop>,
+    event_pump: EventPump,
+    control: EventLoopControl
+}
+
+impl EventLoop {
+    /// Builds an `EventLoop` that drives the given `EventPump`.
+    ///
+    /// Like `EventPump`, at most one `EventLoop` may be alive at a time.
+    pub fn new(sdl: &Sdl, event_pump: EventPump) -> Result<EventLoop, SdlError> {
+        unsafe {
+            if IS_EVENT_LOOP_ALIVE {
+                Err(SdlError::new(SdlErrorKind::AlreadyAlive,
+                                   "an `EventLoop` instance is already alive - there can only be one `EventLoop` in use at a time.".to_owned()))
+            } else {
+                IS_EVENT_LOOP_ALIVE = true;
+
+                Ok(EventLoop {
+                    _sdldrop: sdl.sdldrop.clone(),
+                    event_pump: event_pump,
+                    control: EventLoopControl {
+                        paused: Arc::new(AtomicBool::new(false)),
+                        should_quit: Arc::new(AtomicBool::new(false)),
+                        should_flush: Arc::new(AtomicBool::new(false))
+                    }
+                })
+            }
+        }
+    }
+
+    /// Returns an `EventLoopControl` handle for this loop. Unlike `&EventLoop`,
+    /// this can be cloned and moved onto another thread, or captured by the
+    /// `run` callback itself, to reach `pause`/`resume`/`quit` while `run` is
+    /// still looping.
+    #[inline]
+    pub fn control(&self) -> EventLoopControl {
+        self.control.clone()
+    }
+
+    /// Runs `callback` once per iteration until `quit` is called.
+    ///
+    /// Each iteration first drains jobs scheduled via a `MainThreadProxy` (so a
+    /// `pause`/`resume`/`quit`/`flush` requested from another thread takes
+    /// effect here, between iterations, never in the middle of one), then
+    /// honors any pending `EventLoopControl::flush` request - including one
+    /// requested in the same batch as `quit`, so a "flush on the way out"
+    /// never silently loses to the quit it was requested alongside. Then,
+    /// unless the loop is paused, `callback` is invoked with a borrow of the
+    /// `Sdl` context, the `EventPump`, and an `EventLoopControl` handle for
+    /// this same loop.
+    pub fn run<F>(&mut self, sdl: &Sdl, mut callback: F)
+        where F: FnMut(&Sdl, &mut EventPump, &EventLoopControl)
+    {
+        let control = self.control.clone();
+
+        loop {
+            self.event_pump.run_main_thread_jobs(sdl);
+
+            if control.take_should_flush() {
+                unsafe { ll::SDL_FlushEvents(ll::SDL_FIRSTEVENT, ll::SDL_LASTEVENT); }
+                control.resume();
+            }
+
+            // Checked after draining a pending flush, not in the loop
+            // condition, so a `flush` requested alongside `quit` (e.g. from
+            // another thread, in the same batch) is still honored before
+            // `run` returns.
+            if control.should_quit() {
+                break;
+            }
+
+            if control.is_paused() {
+                // Avoid busy-waiting while paused. A full `EventPump` offers
+                // `SDL_WaitEventTimeout` for this; this chunk doesn't, so a
+                // short sleep stands in for it.
+                ::std::thread::sleep(::std::time::Duration::from_millis(10));
+            } else {
+                callback(sdl, &mut self.event_pump, &control);
+            }
+        }
+    }
+
+    /// Requests that the loop pause after the current iteration finishes.
+    /// Equivalent to `self.control().pause()`.
+    #[inline]
+    pub fn pause(&self) {
+        self.control.pause();
+    }
+
+    /// Resumes invoking `callback` once per iteration.
+    /// Equivalent to `self.control().resume()`.
+    #[inline]
+    pub fn resume(&self) {
+        self.control.resume();
+    }
+
+    /// Purges every event currently sitting in the SDL queue, then re-enables
+    /// delivery (undoing any prior `pause`). Events posted after this call are
+    /// unaffected.
+    ///
+    /// This runs the flush immediately, which requires `&mut EventLoop` - so
+    /// it's only reachable before `run` starts or after it returns, the same
+    /// as `run` itself. From inside the `run` callback, or from another
+    /// thread while `run` is looping, use `self.control().flush()` (or the
+    /// `EventLoopControl` handle `run` passes to the callback) instead; `run`
+    /// performs the same flush on its own thread the next time it checks.
+    pub fn flush(&mut self) {
+        unsafe { ll::SDL_FlushEvents(ll::SDL_FIRSTEVENT, ll::SDL_LASTEVENT); }
+        self.control.resume();
+    }
+
+    /// Requests that `run` stop after the current iteration finishes.
+    /// Equivalent to `self.control().quit()`.
+    #[inline]
+    pub fn quit(&self) {
+        self.control.quit();
+    }
+}
+
+impl Drop for EventLoop {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            assert!(IS_EVENT_LOOP_ALIVE);
+            IS_EVENT_LOOP_ALIVE = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod event_loop_tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn pause_resume_and_quit_are_reachable_from_the_callback() {
+        let sdl = ::init().unwrap();
+        let mut event_loop = sdl.event_loop().unwrap();
+
+        let iterations = Rc::new(Cell::new(0u32));
+        let paused_at = Rc::new(Cell::new(None));
+
+        {
+            let iterations = iterations.clone();
+            let paused_at = paused_at.clone();
+
+            event_loop.run(&sdl, move |_sdl, _pump, control| {
+                let n = iterations.get() + 1;
+                iterations.set(n);
+
+                if n == 3 {
+                    control.pause();
+                    paused_at.set(Some(n));
+                    control.resume();
+                }
+
+                if n == 5 {
+                    control.quit();
+                }
+            });
+        }
+
+        assert_eq!(iterations.get(), 5);
+        assert_eq!(paused_at.get(), Some(3));
+    }
+
+    #[test]
+    fn flush_is_reachable_from_the_callback() {
+        let sdl = ::init().unwrap();
+        let mut event_loop = sdl.event_loop().unwrap();
+
+        let iterations = Rc::new(Cell::new(0u32));
+        let flushed_at = Rc::new(Cell::new(None));
+
+        {
+            let iterations = iterations.clone();
+            let flushed_at = flushed_at.clone();
+
+            event_loop.run(&sdl, move |_sdl, _pump, control| {
+                let n = iterations.get() + 1;
+                iterations.set(n);
+
+                if n == 2 {
+                    control.flush();
+                    flushed_at.set(Some(n));
+                }
+
+                if n == 4 {
+                    control.quit();
+                }
+            });
+        }
+
+        assert_eq!(iterations.get(), 4);
+        assert_eq!(flushed_at.get(), Some(2));
+    }
+
+    #[test]
+    fn quit_is_reachable_from_another_thread() {
+        let sdl = ::init().unwrap();
+        let mut event_loop = sdl.event_loop().unwrap();
+        let control = event_loop.control();
+
+        ::std::thread::spawn(move || {
+            ::std::thread::sleep(Duration::from_millis(20));
+            control.quit();
+        });
+
+        let mut iterations = 0u32;
+        event_loop.run(&sdl, |_sdl, _pump, _control| {
+            iterations += 1;
+        });
+
+        assert!(iterations > 0);
+    }
+
+    #[test]
+    fn flush_requested_alongside_quit_is_still_honored() {
+        let sdl = ::init().unwrap();
+        let mut event_loop = sdl.event_loop().unwrap();
+        let ran = Rc::new(Cell::new(false));
+
+        {
+            let ran = ran.clone();
+
+            event_loop.run(&sdl, move |_sdl, _pump, control| {
+                // Requesting both in the same iteration used to race: `quit`
+                // being checked before the pending `flush` was drained would
+                // let `run` return without ever honoring the flush.
+                // The `pause` lets the test see the flush's resume afterwards.
+                control.pause();
+                control.flush();
+                control.quit();
+                ran.set(true);
+            });
+        }
+
+        assert!(ran.get());
+
+        // An honored flush consumes the request and undoes the pause.
+        let control = event_loop.control();
+        assert!(!control.take_should_flush());
+        assert!(!control.is_paused());
     }
 }
 
@@ -279,7 +856,7 @@ impl Drop for EventPump {
 /// // SDL_Quit() is called here as `sdl_context` is dropped.
 /// ```
 #[inline]
-pub fn init() -> Result<Sdl, String> { Sdl::new() }
+pub fn init() -> Result<Sdl, SdlError> { Sdl::new() }
 
 pub fn get_error() -> String {
     unsafe {